@@ -0,0 +1,67 @@
+//! Durable Object powering the live admin signup feed (`/api/admin/stream`).
+//!
+//! One instance (a single well-known name) holds every connected admin
+//! WebSocket session. The three signup handlers forward new
+//! [`SignupRecord`](crate::SignupRecord)s here as plain HTTP POSTs, which the
+//! object then fans out to each connected socket.
+
+use worker::*;
+
+/// Well-known Durable Object id all signup handlers and the stream route
+/// agree on, so they all talk to the same instance.
+pub const INSTANCE_NAME: &str = "broadcast";
+
+#[durable_object]
+pub struct AdminStream {
+    state: State,
+}
+
+#[durable_object]
+impl DurableObject for AdminStream {
+    fn new(state: State, _env: Env) -> Self {
+        Self { state }
+    }
+
+    async fn fetch(&mut self, req: Request) -> Result<Response> {
+        match req.path().as_str() {
+            "/connect" => {
+                let pair = WebSocketPair::new()?;
+                self.state.accept_web_socket(&pair.server);
+                Response::from_websocket(pair.client)
+            }
+            "/broadcast" => {
+                let body = req.text().await?;
+                for ws in self.state.get_websockets() {
+                    let _ = ws.send_with_str(&body);
+                }
+                Response::ok("broadcast")
+            }
+            _ => Response::error("Not found", 404),
+        }
+    }
+
+    async fn websocket_message(
+        &mut self,
+        ws: WebSocket,
+        message: WebSocketIncomingMessage,
+    ) -> Result<()> {
+        if let WebSocketIncomingMessage::String(text) = message {
+            if text == "ping" {
+                ws.send_with_str("pong")?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn websocket_close(
+        &mut self,
+        _ws: WebSocket,
+        _code: usize,
+        _reason: String,
+        _was_clean: bool,
+    ) -> Result<()> {
+        // Hibernation API drops closed sockets from `get_websockets()`
+        // automatically - nothing to clean up here.
+        Ok(())
+    }
+}
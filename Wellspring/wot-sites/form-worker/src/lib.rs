@@ -8,6 +8,8 @@
 use serde::{Deserialize, Serialize};
 use worker::*;
 
+mod admin_stream;
+
 /// Form submission from now.pub - identity name reservation
 #[derive(Debug, Deserialize, Serialize)]
 struct NowPubSignup {
@@ -38,12 +40,17 @@ struct WotTechnologySignup {
 }
 
 /// Unified storage record
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct SignupRecord {
     source: String,
     email: String,
     timestamp: String,
     data: serde_json::Value,
+    /// Opaque confirmation token. Names the record's `pending:` key, and for
+    /// now.pub also ties the subdomain claim to this signup; untouched once
+    /// confirmed.
+    token: String,
+    confirmed: bool,
 }
 
 /// API response
@@ -53,6 +60,15 @@ struct ApiResponse {
     message: String,
 }
 
+/// Operational snapshot returned by `/api/admin/diagnostics`.
+#[derive(Debug, Serialize)]
+struct DiagnosticsResponse {
+    worker_version: String,
+    server_time: String,
+    signup_counts: serde_json::Value,
+    env_configured: serde_json::Value,
+}
+
 fn log_request(req: &Request) {
     console_log!(
         "{} - [{}] \"{}\"",
@@ -62,16 +78,56 @@ fn log_request(req: &Request) {
     );
 }
 
-fn cors_headers(origin: &str, allowed_origins: &str) -> Headers {
+/// Checks a single `CORS_ORIGIN` entry against a request origin.
+///
+/// Supports an exact match, the literal `*`, and a wildcard-subdomain
+/// pattern like `*.now.pub`, which matches `https://alice.now.pub` (but not
+/// `https://now.pub` itself).
+fn origin_matches(pattern: &str, origin: &str) -> bool {
+    let pattern = pattern.trim();
+
+    if pattern == "*" || pattern == origin {
+        return true;
+    }
+
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+        let scheme_sep = match origin.find("://") {
+            Some(idx) => idx + 3,
+            None => return false,
+        };
+        let host = &origin[scheme_sep..];
+        // Require a literal `.` boundary so `*.now.pub` can't be satisfied
+        // by an unrelated host that merely ends in the same characters,
+        // e.g. `evilnow.pub`.
+        return host.ends_with(&format!(".{suffix}"));
+    }
+
+    false
+}
+
+fn cors_headers(origin: &str, allowed_origins: &str, allow_credentials: bool) -> Headers {
     let mut headers = Headers::new();
 
-    // Check if origin is in allowed list
-    let is_allowed = allowed_origins
+    let matched = allowed_origins
         .split(',')
-        .any(|o| o.trim() == origin || o.trim() == "*");
-
-    if is_allowed {
-        headers.set("Access-Control-Allow-Origin", origin).unwrap();
+        .map(|o| o.trim())
+        .find(|o| origin_matches(o, origin));
+
+    if let Some(pattern) = matched {
+        if pattern == "*" {
+            // Reflected verbatim per the CORS spec - identical for every
+            // origin, so no `Vary` is needed.
+            headers.set("Access-Control-Allow-Origin", "*").unwrap();
+        } else {
+            headers.set("Access-Control-Allow-Origin", origin).unwrap();
+            headers.set("Vary", "Origin").unwrap();
+        }
+
+        if allow_credentials {
+            headers
+                .set("Access-Control-Allow-Credentials", "true")
+                .unwrap();
+        }
     }
 
     headers
@@ -85,6 +141,246 @@ fn cors_headers(origin: &str, allowed_origins: &str) -> Headers {
     headers
 }
 
+/// Defensive headers that `main` merges into the shared header set every
+/// route handler attaches to its response. The `/api/admin/stream` route
+/// returns the Durable Object's WebSocket response as is, without them.
+///
+/// `csp` and `permissions_policy` come from env vars so operators can loosen
+/// them for a particular deployment without a recompile.
+fn security_headers(csp: &str, permissions_policy: &str) -> Headers {
+    let mut headers = Headers::new();
+
+    headers
+        .set("X-Content-Type-Options", "nosniff")
+        .unwrap();
+    headers
+        .set("Referrer-Policy", "same-origin")
+        .unwrap();
+    headers
+        .set("Permissions-Policy", permissions_policy)
+        .unwrap();
+    headers.set("Content-Security-Policy", csp).unwrap();
+
+    headers
+}
+
+/// Compares two strings without short-circuiting on the first mismatching
+/// byte, so a timing attack can't be used to guess the admin token.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Checks the admin bearer token (`Authorization: Bearer <token>` or
+/// `X-Admin-Token: <token>`) against `ADMIN_TOKEN`.
+fn is_admin_authorized(req: &Request, env: &Env) -> Result<bool> {
+    let expected = match env.var("ADMIN_TOKEN") {
+        Ok(token) => token.to_string(),
+        Err(_) => return Ok(false),
+    };
+
+    let provided = req.headers().get("X-Admin-Token")?.or_else(|| {
+        req.headers()
+            .get("Authorization")
+            .ok()
+            .flatten()
+            .and_then(|v| v.strip_prefix("Bearer ").map(|s| s.to_string()))
+    });
+
+    Ok(match provided {
+        Some(token) => constant_time_eq(&token, &expected),
+        None => false,
+    })
+}
+
+/// Forwards a newly stored signup to the `AdminStream` Durable Object, which
+/// fans it out to every connected `/api/admin/stream` WebSocket.
+async fn broadcast_signup(env: &Env, record: &SignupRecord) -> Result<()> {
+    let namespace = env.durable_object("ADMIN_STREAM")?;
+    let stub = namespace
+        .id_from_name(admin_stream::INSTANCE_NAME)?
+        .get_stub()?;
+
+    let mut req_init = RequestInit::new();
+    req_init
+        .with_method(Method::Post)
+        .with_body(Some(serde_json::to_string(record)?.into()));
+    let req = Request::new_with_init("https://admin-stream/broadcast", &req_init)?;
+
+    stub.fetch_with_request(req).await?;
+    Ok(())
+}
+
+/// How long an unconfirmed signup's `pending:` record lives before it
+/// expires out of KV and the token becomes invalid.
+const PENDING_CONFIRMATION_TTL_SECS: u64 = 24 * 60 * 60;
+
+fn pending_key(token: &str) -> String {
+    format!("pending:{token}")
+}
+
+/// Generates an opaque, URL-safe confirmation token.
+///
+/// Confirming is the actual security boundary double opt-in exists to
+/// enforce (proof of mailbox control before a subdomain/email claim goes
+/// live), so the token is drawn from a CSPRNG (`getrandom`'s `js` backend,
+/// i.e. `crypto.getRandomValues`) rather than `Math.random()`.
+fn generate_confirmation_token() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("crypto.getRandomValues unavailable");
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Sends the "click to confirm" email via an outbound HTTP email API.
+///
+/// Silently skipped (with a log line) if `EMAIL_API_ENDPOINT`/`EMAIL_API_KEY`
+/// aren't configured, so the worker stays usable in dev/test deployments
+/// that haven't wired up an email provider.
+async fn send_confirmation_email(env: &Env, to: &str, confirm_url: &str) -> Result<()> {
+    let (endpoint, api_key) = match (env.var("EMAIL_API_ENDPOINT"), env.var("EMAIL_API_KEY")) {
+        (Ok(endpoint), Ok(api_key)) => (endpoint.to_string(), api_key.to_string()),
+        _ => {
+            console_log!(
+                "EMAIL_API_ENDPOINT/EMAIL_API_KEY not configured - skipping confirmation email to {}",
+                to
+            );
+            return Ok(());
+        }
+    };
+
+    let body = serde_json::json!({
+        "to": to,
+        "subject": "Confirm your signup",
+        "text": format!("Please confirm your signup by visiting: {confirm_url}"),
+    });
+
+    let mut email_headers = Headers::new();
+    email_headers.set("Authorization", &format!("Bearer {api_key}"))?;
+    email_headers.set("Content-Type", "application/json")?;
+
+    let mut req_init = RequestInit::new();
+    req_init
+        .with_method(Method::Post)
+        .with_headers(email_headers)
+        .with_body(Some(serde_json::to_string(&body)?.into()));
+
+    let req = Request::new_with_init(&endpoint, &req_init)?;
+    Fetch::Request(req).send().await?;
+    Ok(())
+}
+
+/// Builds the `/api/confirm/:token` link the confirmation email points at.
+fn confirmation_url(req: &Request, token: &str) -> Result<String> {
+    let url = req.url()?;
+    Ok(format!(
+        "{}://{}/api/confirm/{}",
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        token
+    ))
+}
+
+fn unauthorized_response(headers: Headers) -> Result<Response> {
+    json_response(
+        &ApiResponse {
+            success: false,
+            message: "Unauthorized".into(),
+        },
+        401,
+        headers,
+    )
+}
+
+/// Per-(IP, route) submission rate limit, backed by a short-TTL KV counter.
+///
+/// Keyed by route so a burst against one endpoint (e.g. `now-pub/signup`)
+/// can't starve another (e.g. `wot-rocks/signup`) sharing the same worker.
+const RATE_LIMIT_WINDOW_SECS: u64 = 60;
+const DEFAULT_RATE_LIMIT_PER_MIN: u32 = 30;
+
+/// Fixed-window counter stored under `ratelimit:<ip>:<route>`. `reset_at` is
+/// a Unix-seconds timestamp carried in the value (not just the KV TTL) so
+/// the window length is fixed at creation instead of sliding forward on
+/// every request that renews the key's `expiration_ttl`.
+#[derive(Debug, Deserialize, Serialize)]
+struct RateLimitCounter {
+    count: u32,
+    reset_at: u64,
+}
+
+/// Returns `Some(response)` with a `429` if `route` has been hit too many
+/// times this minute by the request's `CF-Connecting-IP`, otherwise records
+/// this request against the counter and returns `None`.
+async fn enforce_rate_limit(
+    req: &Request,
+    env: &Env,
+    kv: &kv::KvStore,
+    route: &str,
+    headers: Headers,
+) -> Result<Option<Response>> {
+    let limit: u32 = env
+        .var("RATE_LIMIT_PER_MIN")
+        .ok()
+        .and_then(|v| v.to_string().parse().ok())
+        .unwrap_or(DEFAULT_RATE_LIMIT_PER_MIN);
+
+    let ip = req
+        .headers()
+        .get("CF-Connecting-IP")?
+        .unwrap_or_else(|| "unknown".into());
+    let key = format!("ratelimit:{ip}:{route}");
+    let now_secs = Date::now().as_millis() / 1000;
+
+    let existing: Option<RateLimitCounter> = kv
+        .get(&key)
+        .text()
+        .await?
+        .and_then(|v| serde_json::from_str(&v).ok());
+
+    let counter = match existing {
+        Some(c) if c.reset_at > now_secs => c,
+        _ => RateLimitCounter {
+            count: 0,
+            reset_at: now_secs + RATE_LIMIT_WINDOW_SECS,
+        },
+    };
+
+    if counter.count >= limit {
+        let mut retry_headers = headers;
+        retry_headers.set(
+            "Retry-After",
+            &(counter.reset_at - now_secs).to_string(),
+        )?;
+        return Ok(Some(json_response(
+            &ApiResponse {
+                success: false,
+                message: "Too many requests - please slow down".into(),
+            },
+            429,
+            retry_headers,
+        )?));
+    }
+
+    let next = RateLimitCounter {
+        count: counter.count + 1,
+        reset_at: counter.reset_at,
+    };
+    // KV rejects any `expiration`/`expiration_ttl` under 60s, but `reset_at`
+    // can be only seconds away by the time a later request in the window
+    // writes it - always write with the fixed window length instead and let
+    // `reset_at` (not the KV TTL) be the source of truth for the logical
+    // window boundary.
+    kv.put(&key, serde_json::to_string(&next)?)?
+        .expiration_ttl(RATE_LIMIT_WINDOW_SECS)
+        .execute()
+        .await?;
+
+    Ok(None)
+}
+
 fn json_response(data: &impl Serialize, status: u16, headers: Headers) -> Result<Response> {
     let json = serde_json::to_string(data)?;
     let mut response = Response::ok(json)?;
@@ -103,6 +399,7 @@ async fn handle_now_pub_signup(
     mut req: Request,
     kv: kv::KvStore,
     headers: Headers,
+    env: Env,
 ) -> Result<Response> {
     let signup: NowPubSignup = req.json().await?;
 
@@ -133,7 +430,7 @@ async fn handle_now_pub_signup(
         );
     }
 
-    // Check if subdomain is already reserved
+    // Check if the subdomain is already reserved, confirmed or pending
     let key = format!("nowpub:subdomain:{}", signup.subdomain.to_lowercase());
     if kv.get(&key).text().await?.is_some() {
         return json_response(
@@ -146,28 +443,40 @@ async fn handle_now_pub_signup(
         );
     }
 
-    // Store the reservation
+    let token = generate_confirmation_token();
     let record = SignupRecord {
         source: "now.pub".into(),
         email: signup.email.clone(),
         timestamp: Date::now().to_string(),
         data: serde_json::to_value(&signup)?,
+        token: token.clone(),
+        confirmed: false,
     };
 
+    // Claim the subdomain under its permanent key right away (still expiring
+    // with the confirmation window) so a second submission for the same
+    // subdomain hits the check above instead of also being left pending.
     kv.put(&key, serde_json::to_string(&record)?)?
+        .expiration_ttl(PENDING_CONFIRMATION_TTL_SECS)
         .execute()
         .await?;
-
-    // Also store by email for lookup
-    let email_key = format!("nowpub:email:{}", signup.email.to_lowercase());
-    kv.put(&email_key, &signup.subdomain)?
+    kv.put(&pending_key(&token), serde_json::to_string(&record)?)?
+        .expiration_ttl(PENDING_CONFIRMATION_TTL_SECS)
         .execute()
         .await?;
 
+    broadcast_signup(&env, &record).await?;
+
+    let confirm_url = confirmation_url(&req, &token)?;
+    send_confirmation_email(&env, &signup.email, &confirm_url).await?;
+
     json_response(
         &ApiResponse {
             success: true,
-            message: format!("{}.now.pub has been reserved!", signup.subdomain),
+            message: format!(
+                "Check your email to confirm your reservation for {}.now.pub",
+                signup.subdomain
+            ),
         },
         200,
         headers,
@@ -178,27 +487,34 @@ async fn handle_wot_rocks_signup(
     mut req: Request,
     kv: kv::KvStore,
     headers: Headers,
+    env: Env,
 ) -> Result<Response> {
     let signup: WotRocksSignup = req.json().await?;
 
-    // Store by email
-    let key = format!("wotrocks:email:{}", signup.email.to_lowercase());
-
+    let token = generate_confirmation_token();
     let record = SignupRecord {
         source: "wot.rocks".into(),
         email: signup.email.clone(),
         timestamp: Date::now().to_string(),
         data: serde_json::to_value(&signup)?,
+        token: token.clone(),
+        confirmed: false,
     };
 
-    kv.put(&key, serde_json::to_string(&record)?)?
+    kv.put(&pending_key(&token), serde_json::to_string(&record)?)?
+        .expiration_ttl(PENDING_CONFIRMATION_TTL_SECS)
         .execute()
         .await?;
 
+    broadcast_signup(&env, &record).await?;
+
+    let confirm_url = confirmation_url(&req, &token)?;
+    send_confirmation_email(&env, &signup.email, &confirm_url).await?;
+
     json_response(
         &ApiResponse {
             success: true,
-            message: "You're on the waitlist! We'll be in touch.".into(),
+            message: "Check your email to confirm your spot on the waitlist!".into(),
         },
         200,
         headers,
@@ -209,27 +525,170 @@ async fn handle_wot_technology_signup(
     mut req: Request,
     kv: kv::KvStore,
     headers: Headers,
+    env: Env,
 ) -> Result<Response> {
     let signup: WotTechnologySignup = req.json().await?;
 
-    // Store by email
-    let key = format!("wottech:email:{}", signup.email.to_lowercase());
-
+    let token = generate_confirmation_token();
     let record = SignupRecord {
         source: "wot.technology".into(),
         email: signup.email.clone(),
         timestamp: Date::now().to_string(),
         data: serde_json::to_value(&signup)?,
+        token: token.clone(),
+        confirmed: false,
     };
 
-    kv.put(&key, serde_json::to_string(&record)?)?
+    kv.put(&pending_key(&token), serde_json::to_string(&record)?)?
+        .expiration_ttl(PENDING_CONFIRMATION_TTL_SECS)
         .execute()
         .await?;
 
+    broadcast_signup(&env, &record).await?;
+
+    let confirm_url = confirmation_url(&req, &token)?;
+    send_confirmation_email(&env, &signup.email, &confirm_url).await?;
+
     json_response(
         &ApiResponse {
             success: true,
-            message: "You're on the early access list for the SDK!".into(),
+            message: "Check your email to confirm your spot on the SDK early access list!".into(),
+        },
+        200,
+        headers,
+    )
+}
+
+async fn handle_confirm_signup(token: String, kv: kv::KvStore, headers: Headers) -> Result<Response> {
+    let value = match kv.get(&pending_key(&token)).text().await? {
+        Some(value) => value,
+        None => {
+            return json_response(
+                &ApiResponse {
+                    success: false,
+                    message: "This confirmation link is invalid or has expired".into(),
+                },
+                404,
+                headers,
+            )
+        }
+    };
+
+    let mut record: SignupRecord = serde_json::from_str(&value)?;
+    record.confirmed = true;
+
+    let permanent_key = match record.source.as_str() {
+        "now.pub" => {
+            let subdomain = record
+                .data
+                .get("subdomain")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_lowercase();
+            let subdomain_key = format!("nowpub:subdomain:{subdomain}");
+
+            // The subdomain is claimed under `subdomain_key` as soon as it's
+            // submitted (see handle_now_pub_signup), so confirming is only
+            // valid if that claim still belongs to this token - it may have
+            // expired and been re-claimed by someone else in the meantime.
+            match kv.get(&subdomain_key).text().await? {
+                Some(claim) if serde_json::from_str::<SignupRecord>(&claim)?.token == token => {}
+                _ => {
+                    return json_response(
+                        &ApiResponse {
+                            success: false,
+                            message: "This subdomain reservation has expired".into(),
+                        },
+                        409,
+                        headers,
+                    )
+                }
+            }
+
+            let email_key = format!("nowpub:email:{}", record.email.to_lowercase());
+            kv.put(&email_key, &subdomain)?.execute().await?;
+            subdomain_key
+        }
+        "wot.rocks" => format!("wotrocks:email:{}", record.email.to_lowercase()),
+        "wot.technology" => format!("wottech:email:{}", record.email.to_lowercase()),
+        other => {
+            return json_response(
+                &ApiResponse {
+                    success: false,
+                    message: format!("Unknown signup source '{other}'"),
+                },
+                400,
+                headers,
+            )
+        }
+    };
+
+    kv.put(&permanent_key, serde_json::to_string(&record)?)?
+        .execute()
+        .await?;
+    kv.delete(&pending_key(&token)).await?;
+
+    json_response(
+        &ApiResponse {
+            success: true,
+            message: "Your email has been confirmed!".into(),
+        },
+        200,
+        headers,
+    )
+}
+
+/// Fetches every confirmed `SignupRecord` under a source's KV prefix,
+/// skipping the plain-string email index entries that also live there.
+async fn confirmed_signups(kv: &kv::KvStore, prefix: &str) -> Result<Vec<SignupRecord>> {
+    let list = kv.list().prefix(prefix.into()).execute().await?;
+
+    let mut records = Vec::new();
+    for key in list.keys.into_iter().map(|k| k.name) {
+        if let Some(value) = kv.get(&key).text().await? {
+            if let Ok(record) = serde_json::from_str::<SignupRecord>(&value) {
+                if record.confirmed {
+                    records.push(record);
+                }
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+async fn handle_admin_diagnostics(
+    kv: kv::KvStore,
+    headers: Headers,
+    env: &Env,
+) -> Result<Response> {
+    let mut signup_counts = serde_json::Map::new();
+    for (source, prefix) in [
+        ("now-pub", "nowpub:"),
+        ("wot-rocks", "wotrocks:"),
+        ("wot-technology", "wottech:"),
+    ] {
+        let count = confirmed_signups(&kv, prefix).await?.len();
+        signup_counts.insert(source.into(), count.into());
+    }
+
+    let mut env_configured = serde_json::Map::new();
+    for var in [
+        "CORS_ORIGIN",
+        "ADMIN_TOKEN",
+        "EMAIL_API_ENDPOINT",
+        "EMAIL_API_KEY",
+        "RATE_LIMIT_PER_MIN",
+    ] {
+        env_configured.insert(var.into(), env.var(var).is_ok().into());
+    }
+
+    json_response(
+        &DiagnosticsResponse {
+            worker_version: "0.1.0".into(),
+            server_time: Date::now().to_string(),
+            signup_counts: signup_counts.into(),
+            env_configured: env_configured.into(),
         },
         200,
         headers,
@@ -247,7 +706,26 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .get("Origin")?
         .unwrap_or_else(|| "*".to_string());
 
-    let headers = cors_headers(&origin, &allowed_origins);
+    let allow_credentials = env
+        .var("CORS_ALLOW_CREDENTIALS")
+        .map(|v| v.to_string() == "true")
+        .unwrap_or(false);
+    let mut headers = cors_headers(&origin, &allowed_origins, allow_credentials);
+
+    let csp = env
+        .var("CSP_POLICY")
+        .map(|v| v.to_string())
+        .unwrap_or_else(|_| "default-src 'none'; frame-ancestors 'none'".into());
+    let permissions_policy = env
+        .var("PERMISSIONS_POLICY")
+        .map(|v| v.to_string())
+        .unwrap_or_else(|_| {
+            "camera=(), geolocation=(), microphone=(), usb=(), payment=(), interest-cohort=()"
+                .into()
+        });
+    for (key, value) in security_headers(&csp, &permissions_policy).entries() {
+        headers.set(&key, &value)?;
+    }
 
     // Handle CORS preflight
     if req.method() == Method::Options {
@@ -257,25 +735,49 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     let kv = env.kv("WOT_SIGNUPS")?;
 
     Router::with_data((kv, headers.clone()))
-        .get("/", |_, _| Response::ok("WoT Form Worker v0.1.0"))
-        .get("/health", |_, _| {
-            Response::ok(r#"{"status":"healthy"}"#)
+        .get("/", |_, ctx| {
+            Response::ok("WoT Form Worker v0.1.0")?.with_headers(ctx.data.1.clone())
+        })
+        .get("/health", |_, ctx| {
+            Response::ok(r#"{"status":"healthy"}"#)?.with_headers(ctx.data.1.clone())
         })
         .post_async("/api/now-pub/signup", |req, ctx| async move {
             let (kv, headers) = ctx.data;
-            handle_now_pub_signup(req, kv.clone(), headers.clone()).await
+            let env = ctx.env.clone();
+            if let Some(limited) =
+                enforce_rate_limit(&req, &env, &kv, "now-pub/signup", headers.clone()).await?
+            {
+                return Ok(limited);
+            }
+            handle_now_pub_signup(req, kv.clone(), headers.clone(), env).await
         })
         .post_async("/api/wot-rocks/signup", |req, ctx| async move {
             let (kv, headers) = ctx.data;
-            handle_wot_rocks_signup(req, kv.clone(), headers.clone()).await
+            let env = ctx.env.clone();
+            if let Some(limited) =
+                enforce_rate_limit(&req, &env, &kv, "wot-rocks/signup", headers.clone()).await?
+            {
+                return Ok(limited);
+            }
+            handle_wot_rocks_signup(req, kv.clone(), headers.clone(), env).await
         })
         .post_async("/api/wot-technology/signup", |req, ctx| async move {
             let (kv, headers) = ctx.data;
-            handle_wot_technology_signup(req, kv.clone(), headers.clone()).await
+            let env = ctx.env.clone();
+            if let Some(limited) =
+                enforce_rate_limit(&req, &env, &kv, "wot-technology/signup", headers.clone())
+                    .await?
+            {
+                return Ok(limited);
+            }
+            handle_wot_technology_signup(req, kv.clone(), headers.clone(), env).await
         })
-        // List signups (protected - add auth in production)
-        .get_async("/api/signups/:source", |_req, ctx| async move {
+        // List signups (protected by admin token)
+        .get_async("/api/signups/:source", |req, ctx| async move {
             let (kv, headers) = ctx.data;
+            if !is_admin_authorized(&req, &ctx.env)? {
+                return unauthorized_response(headers.clone());
+            }
             let source = ctx.param("source").unwrap();
 
             let prefix = match source.as_str() {
@@ -294,18 +796,117 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
                 }
             };
 
-            let list = kv.list().prefix(prefix.into()).execute().await?;
-            let keys: Vec<String> = list.keys.into_iter().map(|k| k.name).collect();
-
-            let mut entries = Vec::new();
-            for key in keys {
-                if let Some(value) = kv.get(&key).text().await? {
-                    entries.push(serde_json::from_str::<serde_json::Value>(&value)?);
-                }
+            let records = confirmed_signups(&kv, prefix).await?;
+            json_response(&records, 200, headers.clone())
+        })
+        // Confirm a pending signup from its emailed link
+        .get_async("/api/confirm/:token", |req, ctx| async move {
+            let (kv, headers) = ctx.data;
+            let env = ctx.env.clone();
+            if let Some(limited) =
+                enforce_rate_limit(&req, &env, &kv, "confirm", headers.clone()).await?
+            {
+                return Ok(limited);
+            }
+            let token = ctx.param("token").unwrap().clone();
+            handle_confirm_signup(token, kv.clone(), headers.clone()).await
+        })
+        // Operational diagnostics (protected by admin token)
+        .get_async("/api/admin/diagnostics", |req, ctx| async move {
+            let (kv, headers) = ctx.data;
+            if !is_admin_authorized(&req, &ctx.env)? {
+                return unauthorized_response(headers.clone());
+            }
+            handle_admin_diagnostics(kv.clone(), headers.clone(), &ctx.env).await
+        })
+        // Live signup feed over WebSocket (protected by admin token)
+        .get_async("/api/admin/stream", |req, ctx| async move {
+            let (_kv, headers) = ctx.data;
+            if !is_admin_authorized(&req, &ctx.env)? {
+                return unauthorized_response(headers.clone());
             }
 
-            json_response(&entries, 200, headers.clone())
+            let namespace = ctx.env.durable_object("ADMIN_STREAM")?;
+            let stub = namespace
+                .id_from_name(admin_stream::INSTANCE_NAME)?
+                .get_stub()?;
+            stub.fetch_with_str("https://admin-stream/connect").await
         })
         .run(req, env)
         .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn origin_matches_exact() {
+        assert!(origin_matches("https://wot.rocks", "https://wot.rocks"));
+        assert!(!origin_matches("https://wot.rocks", "https://wot.technology"));
+    }
+
+    #[test]
+    fn origin_matches_wildcard_subdomain() {
+        assert!(origin_matches("*.now.pub", "https://alice.now.pub"));
+        assert!(origin_matches("*.now.pub", "https://deeply.nested.now.pub"));
+    }
+
+    #[test]
+    fn origin_matches_wildcard_rejects_lookalike_suffix() {
+        assert!(!origin_matches("*.now.pub", "https://evilnow.pub"));
+        assert!(!origin_matches("*.now.pub", "https://shadynow.pub"));
+        assert!(!origin_matches("*.now.pub", "https://now.pub"));
+    }
+
+    #[test]
+    fn origin_matches_star() {
+        assert!(origin_matches("*", "https://anything.example"));
+    }
+
+    #[test]
+    fn cors_headers_exact_match_sets_acao_and_vary() {
+        let headers = cors_headers("https://wot.rocks", "https://wot.rocks", false);
+        assert_eq!(
+            headers.get("Access-Control-Allow-Origin").unwrap(),
+            Some("https://wot.rocks".to_string())
+        );
+        assert_eq!(headers.get("Vary").unwrap(), Some("Origin".to_string()));
+    }
+
+    #[test]
+    fn cors_headers_wildcard_subdomain_sets_acao_and_vary() {
+        let headers = cors_headers("https://alice.now.pub", "*.now.pub", false);
+        assert_eq!(
+            headers.get("Access-Control-Allow-Origin").unwrap(),
+            Some("https://alice.now.pub".to_string())
+        );
+        assert_eq!(headers.get("Vary").unwrap(), Some("Origin".to_string()));
+    }
+
+    #[test]
+    fn cors_headers_disallowed_origin_sets_no_acao() {
+        let headers = cors_headers("https://evil.example", "https://wot.rocks", false);
+        assert_eq!(headers.get("Access-Control-Allow-Origin").unwrap(), None);
+        assert_eq!(headers.get("Vary").unwrap(), None);
+    }
+
+    #[test]
+    fn cors_headers_star_reflects_literal_wildcard_without_vary() {
+        let headers = cors_headers("https://anything.example", "*", false);
+        assert_eq!(
+            headers.get("Access-Control-Allow-Origin").unwrap(),
+            Some("*".to_string())
+        );
+        assert_eq!(headers.get("Vary").unwrap(), None);
+    }
+
+    #[test]
+    fn cors_headers_credentials_flag() {
+        let headers = cors_headers("https://wot.rocks", "https://wot.rocks", true);
+        assert_eq!(
+            headers.get("Access-Control-Allow-Credentials").unwrap(),
+            Some("true".to_string())
+        );
+    }
+}